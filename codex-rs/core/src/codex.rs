@@ -3789,7 +3789,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::reload_user_config(&sess).await;
             }
             Op::ListCustomPrompts => {
-                handlers::list_custom_prompts(&sess, sub.id.clone()).await;
+                handlers::list_custom_prompts(&sess, &config, sub.id.clone()).await;
             }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
@@ -4209,10 +4209,11 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
-    pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
+    pub async fn list_custom_prompts(sess: &Session, config: &Arc<Config>, sub_id: String) {
+        let max_file_bytes = config.custom_prompt_max_bytes;
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                crate::custom_prompts::discover_prompts_in(&dir).await
+                crate::custom_prompts::discover_prompts_in(&dir, max_file_bytes).await
             } else {
                 Vec::new()
             };
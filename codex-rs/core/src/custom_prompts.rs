@@ -1,8 +1,11 @@
 use codex_protocol::custom_prompts::CustomPrompt;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::fs;
+use toml::Value as TomlValue;
 
 /// Return the default prompts directory: `$CODEX_HOME/prompts`.
 /// If `CODEX_HOME` cannot be resolved, returns `None`.
@@ -14,15 +17,18 @@ pub fn default_prompts_dir() -> Option<PathBuf> {
 
 /// Discover prompt files in the given directory, returning entries sorted by name.
 /// Non-files are ignored. If the directory does not exist or cannot be read, returns empty.
-pub async fn discover_prompts_in(dir: &Path) -> Vec<CustomPrompt> {
-    discover_prompts_in_excluding(dir, &HashSet::new()).await
+pub async fn discover_prompts_in(dir: &Path, max_file_bytes: usize) -> Vec<CustomPrompt> {
+    discover_prompts_in_excluding(dir, &HashSet::new(), max_file_bytes).await
 }
 
 /// Discover prompt files in the given directory, excluding any with names in `exclude`.
-/// Returns entries sorted by name. Non-files are ignored. Missing/unreadable dir yields empty.
+/// Returns entries sorted by name. Non-files are ignored, and files over
+/// `max_file_bytes` are skipped with a warning before they are read.
+/// Missing/unreadable dir yields empty.
 pub async fn discover_prompts_in_excluding(
     dir: &Path,
     exclude: &HashSet<String>,
+    max_file_bytes: usize,
 ) -> Vec<CustomPrompt> {
     let mut out: Vec<CustomPrompt> = Vec::new();
     let mut entries = match fs::read_dir(dir).await {
@@ -32,11 +38,10 @@ pub async fn discover_prompts_in_excluding(
 
     while let Ok(Some(entry)) = entries.next_entry().await {
         let path = entry.path();
-        let is_file_like = fs::metadata(&path)
-            .await
-            .map(|m| m.is_file())
-            .unwrap_or(false);
-        if !is_file_like {
+        let Ok(metadata) = fs::metadata(&path).await else {
+            continue;
+        };
+        if !metadata.is_file() {
             continue;
         }
         // Only include Markdown files with a .md extension.
@@ -58,44 +63,89 @@ pub async fn discover_prompts_in_excluding(
         if exclude.contains(&name) {
             continue;
         }
+        if metadata.len() > max_file_bytes as u64 {
+            tracing::warn!(
+                "Skipping prompt file `{}`: {} bytes exceeds the {} byte limit.",
+                path.display(),
+                metadata.len(),
+                max_file_bytes,
+            );
+            continue;
+        }
         let content = match fs::read_to_string(&path).await {
             Ok(s) => s,
             Err(_) => continue,
         };
-        let (description, argument_hint, body) = parse_frontmatter(&content);
+        let parsed = parse_frontmatter(&content);
         out.push(CustomPrompt {
             name,
             path,
-            content: body,
-            description,
-            argument_hint,
+            content: parsed.body,
+            description: parsed.description,
+            argument_hint: parsed.argument_hint,
+            default_args: parsed.default_args,
         });
     }
     out.sort_by(|a, b| a.name.cmp(&b.name));
     out
 }
 
+/// Metadata and body extracted from a prompt file's frontmatter.
+#[derive(Debug, Default, PartialEq)]
+struct ParsedFrontmatter {
+    description: Option<String>,
+    argument_hint: Option<String>,
+    default_args: Option<HashMap<String, String>>,
+    /// `#` comment lines from a YAML-like block as (zero-based line index in the
+    /// file, line text), so a rewriter can reattach them.
+    #[allow(dead_code)] // Nothing rewrites frontmatter yet.
+    comments: Vec<(usize, String)>,
+    body: String,
+}
+
+impl ParsedFrontmatter {
+    fn body_only(content: &str) -> Self {
+        Self {
+            body: content.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Parse optional YAML-like frontmatter at the beginning of `content`.
 /// Supported keys:
 /// - `description`: short description shown in the slash popup
 /// - `argument-hint` or `argument_hint`: brief hint string shown after the description
-///   Returns (description, argument_hint, body_without_frontmatter).
-fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String) {
+/// - `default-args` or `default_args`: default values for named placeholders,
+///   either as a flow map (`default-args: {BRANCH: main}`) or as a block mapping
+///   of indented `BRANCH: main` lines under an empty `default-args:`
+///
+/// A `+++`-fenced block is parsed as TOML and a `;;;`-fenced block as JSON, with
+/// the same keys. A leading UTF-8 BOM is ignored so files saved by Windows
+/// editors are still recognized.
+fn parse_frontmatter(content: &str) -> ParsedFrontmatter {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if let Some(parsed) = parse_structured_frontmatter(content) {
+        return parsed;
+    }
     let mut segments = content.split_inclusive('\n');
     let Some(first_segment) = segments.next() else {
-        return (None, None, String::new());
+        return ParsedFrontmatter::default();
     };
     let first_line = first_segment.trim_end_matches(['\r', '\n']);
     if first_line.trim() != "---" {
-        return (None, None, content.to_string());
+        return ParsedFrontmatter::body_only(content);
     }
 
-    let mut desc: Option<String> = None;
-    let mut hint: Option<String> = None;
+    let mut parsed = ParsedFrontmatter::default();
     let mut frontmatter_closed = false;
     let mut consumed = first_segment.len();
+    // Zero-based index of the current line; the opening fence is line 0.
+    let mut line_index = 0;
 
-    for segment in segments {
+    let mut segments = segments.peekable();
+    while let Some(segment) = segments.next() {
+        line_index += 1;
         let line = segment.trim_end_matches(['\r', '\n']);
         let trimmed = line.trim();
 
@@ -105,6 +155,9 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
             break;
         }
 
+        if trimmed.starts_with('#') {
+            parsed.comments.push((line_index, line.to_string()));
+        }
         if trimmed.is_empty() || trimmed.starts_with('#') {
             consumed += segment.len();
             continue;
@@ -122,8 +175,31 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
                 }
             }
             match key.as_str() {
-                "description" => desc = Some(val),
-                "argument-hint" | "argument_hint" => hint = Some(val),
+                "description" => parsed.description = Some(val),
+                "argument-hint" | "argument_hint" => parsed.argument_hint = Some(val),
+                "default-args" | "default_args" => {
+                    let mut value = val;
+                    if value.is_empty() {
+                        // Block mapping: the entries are the indented lines that follow.
+                        while let Some(entry) = segments.next_if(|next| {
+                            next.starts_with([' ', '\t'])
+                                && !next.trim().is_empty()
+                                && next.trim() != "---"
+                        }) {
+                            line_index += 1;
+                            consumed += entry.len();
+                            if entry.trim_start().starts_with('#') {
+                                let comment = entry.trim_end_matches(['\r', '\n']);
+                                parsed.comments.push((line_index, comment.to_string()));
+                                continue;
+                            }
+                            value.push_str(entry.trim_start());
+                        }
+                    }
+                    parsed.default_args = serde_yaml::from_str::<JsonValue>(&value)
+                        .ok()
+                        .and_then(default_args_from_value);
+                }
                 _ => {}
             }
         }
@@ -133,20 +209,100 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
 
     if !frontmatter_closed {
         // Unterminated frontmatter: treat input as-is.
-        return (None, None, content.to_string());
+        return ParsedFrontmatter::body_only(content);
     }
 
-    let body = if consumed >= content.len() {
+    parsed.body = if consumed >= content.len() {
         String::new()
     } else {
         content[consumed..].to_string()
     };
-    (desc, hint, body)
+    parsed
+}
+
+/// Parse structured frontmatter opened on the first non-empty line: Hugo-style
+/// `+++` fences hold TOML and `;;;` fences hold a JSON object.
+/// Returns `None` when there is no such fence or the block is unterminated, so
+/// the caller falls back to the YAML-like parser (which keeps the input as-is).
+fn parse_structured_frontmatter(content: &str) -> Option<ParsedFrontmatter> {
+    let mut segments = content.split_inclusive('\n');
+    let mut consumed = 0;
+    let mut fence = None;
+    for segment in segments.by_ref() {
+        consumed += segment.len();
+        let trimmed = segment.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        fence = ["+++", ";;;"].into_iter().find(|fence| *fence == trimmed);
+        break;
+    }
+    let fence = fence?;
+
+    let block_start = consumed;
+    for segment in segments {
+        if segment.trim() == fence {
+            let block = &content[block_start..consumed];
+            consumed += segment.len();
+            // Malformed blocks are ignored like unknown YAML-like lines: the block is
+            // still stripped from the body but contributes no metadata.
+            let value = if fence == "+++" {
+                toml::from_str::<TomlValue>(block)
+                    .ok()
+                    .and_then(|value| serde_json::to_value(value).ok())
+            } else {
+                serde_json::from_str::<JsonValue>(block).ok()
+            };
+            let mut parsed = ParsedFrontmatter::body_only(&content[consumed..]);
+            if let Some(JsonValue::Object(map)) = value {
+                for (key, value) in map {
+                    match key.to_ascii_lowercase().as_str() {
+                        "description" => {
+                            parsed.description = value.as_str().map(str::to_string);
+                        }
+                        "argument-hint" | "argument_hint" => {
+                            parsed.argument_hint = value.as_str().map(str::to_string);
+                        }
+                        "default-args" | "default_args" => {
+                            parsed.default_args = default_args_from_value(value);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            return Some(parsed);
+        }
+        consumed += segment.len();
+    }
+    None
+}
+
+/// Convert a `default-args` mapping into placeholder defaults. Scalar values are
+/// stringified; nested values are skipped. Returns `None` for non-maps and
+/// maps without usable entries.
+fn default_args_from_value(value: JsonValue) -> Option<HashMap<String, String>> {
+    let JsonValue::Object(map) = value else {
+        return None;
+    };
+    let defaults: HashMap<String, String> = map
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                JsonValue::String(s) => s,
+                JsonValue::Number(n) => n.to_string(),
+                JsonValue::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some((key, value))
+        })
+        .collect();
+    (!defaults.is_empty()).then_some(defaults)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CUSTOM_PROMPT_MAX_BYTES;
     use std::fs;
     use tempfile::tempdir;
 
@@ -154,7 +310,7 @@ mod tests {
     async fn empty_when_dir_missing() {
         let tmp = tempdir().expect("create TempDir");
         let missing = tmp.path().join("nope");
-        let found = discover_prompts_in(&missing).await;
+        let found = discover_prompts_in(&missing, CUSTOM_PROMPT_MAX_BYTES).await;
         assert!(found.is_empty());
     }
 
@@ -165,7 +321,7 @@ mod tests {
         fs::write(dir.join("b.md"), b"b").unwrap();
         fs::write(dir.join("a.md"), b"a").unwrap();
         fs::create_dir(dir.join("subdir")).unwrap();
-        let found = discover_prompts_in(dir).await;
+        let found = discover_prompts_in(dir, CUSTOM_PROMPT_MAX_BYTES).await;
         let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
         assert_eq!(names, vec!["a", "b"]);
     }
@@ -178,7 +334,7 @@ mod tests {
         fs::write(dir.join("foo.md"), b"ok").unwrap();
         let mut exclude = HashSet::new();
         exclude.insert("init".to_string());
-        let found = discover_prompts_in_excluding(dir, &exclude).await;
+        let found = discover_prompts_in_excluding(dir, &exclude, CUSTOM_PROMPT_MAX_BYTES).await;
         let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
         assert_eq!(names, vec!["foo"]);
     }
@@ -191,11 +347,29 @@ mod tests {
         fs::write(dir.join("good.md"), b"hello").unwrap();
         // Invalid UTF-8 content in .md file (e.g., lone 0xFF byte)
         fs::write(dir.join("bad.md"), vec![0xFF, 0xFE, b'\n']).unwrap();
-        let found = discover_prompts_in(dir).await;
+        let found = discover_prompts_in(dir, CUSTOM_PROMPT_MAX_BYTES).await;
         let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
         assert_eq!(names, vec!["good"]);
     }
 
+    #[tokio::test]
+    async fn skips_oversized_files() {
+        let tmp = tempdir().expect("create TempDir");
+        let dir = tmp.path();
+        let limit = CUSTOM_PROMPT_MAX_BYTES;
+        fs::write(dir.join("huge.md"), vec![b'a'; limit + 1]).unwrap();
+        fs::write(dir.join("limit.md"), vec![b'a'; limit]).unwrap();
+        let found = discover_prompts_in(dir, limit).await;
+        let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["limit"]);
+
+        // A configured limit applies instead of the default.
+        fs::write(dir.join("small.md"), b"short").unwrap();
+        let found = discover_prompts_in(dir, "short".len()).await;
+        let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["small"]);
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn discovers_symlinked_md_files() {
@@ -208,7 +382,7 @@ mod tests {
         // Create a symlink to the real file
         std::os::unix::fs::symlink(dir.join("real.md"), dir.join("link.md")).unwrap();
 
-        let found = discover_prompts_in(dir).await;
+        let found = discover_prompts_in(dir, CUSTOM_PROMPT_MAX_BYTES).await;
         let names: Vec<String> = found.into_iter().map(|e| e.name).collect();
 
         // Both real and link should be discovered, sorted alphabetically
@@ -223,7 +397,7 @@ mod tests {
         let text = "---\nname: ignored\ndescription: \"Quick review command\"\nargument-hint: \"[file] [priority]\"\n---\nActual body with $1 and $ARGUMENTS";
         fs::write(&file, text).unwrap();
 
-        let found = discover_prompts_in(dir).await;
+        let found = discover_prompts_in(dir, CUSTOM_PROMPT_MAX_BYTES).await;
         assert_eq!(found.len(), 1);
         let p = &found[0];
         assert_eq!(p.name, "withmeta");
@@ -236,9 +410,128 @@ mod tests {
     #[test]
     fn parse_frontmatter_preserves_body_newlines() {
         let content = "---\r\ndescription: \"Line endings\"\r\nargument_hint: \"[arg]\"\r\n---\r\nFirst line\r\nSecond line\r\n";
-        let (desc, hint, body) = parse_frontmatter(content);
-        assert_eq!(desc.as_deref(), Some("Line endings"));
-        assert_eq!(hint.as_deref(), Some("[arg]"));
-        assert_eq!(body, "First line\r\nSecond line\r\n");
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description.as_deref(), Some("Line endings"));
+        assert_eq!(parsed.argument_hint.as_deref(), Some("[arg]"));
+        assert_eq!(parsed.body, "First line\r\nSecond line\r\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_keys_are_case_insensitive() {
+        let content = "---\nDescription: Mixed case\nARGUMENT-HINT: \"[file]\"\n---\nBody";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description.as_deref(), Some("Mixed case"));
+        assert_eq!(parsed.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(parsed.body, "Body");
+    }
+
+    #[test]
+    fn parse_frontmatter_accepts_toml_fence() {
+        let content = "+++\ndescription = \"TOML review\"\nargument-hint = \"[file]\"\nallowed-tools = [\"shell\"]\n+++\nBody $1\n";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description.as_deref(), Some("TOML review"));
+        assert_eq!(parsed.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(parsed.body, "Body $1\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_keeps_unterminated_toml_fence_as_body() {
+        let content = "+++\ndescription = \"never closed\"\nBody";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description, None);
+        assert_eq!(parsed.argument_hint, None);
+        assert_eq!(parsed.body, content);
+    }
+
+    #[test]
+    fn parse_frontmatter_accepts_json_fence() {
+        let content = ";;;\n{\"description\": \"JSON review\", \"model\": \"gpt-5\"}\n;;;\nBody\n";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description.as_deref(), Some("JSON review"));
+        assert_eq!(parsed.argument_hint, None);
+        assert_eq!(parsed.body, "Body\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_reads_default_args_flow_map() {
+        let content = "---\ndefault-args: {BRANCH: main, RETRIES: 3}\n---\nDeploy $BRANCH";
+        let parsed = parse_frontmatter(content);
+        let expected = HashMap::from([
+            ("BRANCH".to_string(), "main".to_string()),
+            ("RETRIES".to_string(), "3".to_string()),
+        ]);
+        assert_eq!(parsed.default_args, Some(expected));
+        assert_eq!(parsed.body, "Deploy $BRANCH");
+    }
+
+    #[test]
+    fn parse_frontmatter_reads_default_args_block_mapping() {
+        let content = "---\ndefault-args:\n  BRANCH: main\n  RETRIES: 3\nargument-hint: \"[SERVICE]\"\n---\nDeploy $SERVICE";
+        let parsed = parse_frontmatter(content);
+        let expected = HashMap::from([
+            ("BRANCH".to_string(), "main".to_string()),
+            ("RETRIES".to_string(), "3".to_string()),
+        ]);
+        assert_eq!(parsed.default_args, Some(expected));
+        assert_eq!(parsed.argument_hint.as_deref(), Some("[SERVICE]"));
+        assert_eq!(parsed.body, "Deploy $SERVICE");
+    }
+
+    #[test]
+    fn parse_frontmatter_reads_default_args_table() {
+        let content = "+++\n[default_args]\nBRANCH = \"main\"\n+++\nDeploy $BRANCH";
+        let parsed = parse_frontmatter(content);
+        let expected = HashMap::from([("BRANCH".to_string(), "main".to_string())]);
+        assert_eq!(parsed.default_args, Some(expected));
+    }
+
+    #[test]
+    fn parse_frontmatter_ignores_leading_bom() {
+        let content = "\u{feff}---\ndescription: Saved on Windows\n---\nBody";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.description.as_deref(), Some("Saved on Windows"));
+        assert_eq!(parsed.body, "Body");
+    }
+
+    #[test]
+    fn parse_frontmatter_handles_bom_with_crlf_fences() {
+        let content = "\u{feff}---\r\nargument-hint: \"[file]\"\r\n---\r\nBody\r\n";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(parsed.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(parsed.body, "Body\r\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_keeps_comment_lines_with_positions() {
+        let content = "---\n# reviewed weekly\ndescription: Review\n  # keep short\n---\nBody";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(
+            parsed.comments,
+            vec![
+                (1, "# reviewed weekly".to_string()),
+                (3, "  # keep short".to_string()),
+            ]
+        );
+        assert_eq!(parsed.description.as_deref(), Some("Review"));
+        assert_eq!(parsed.body, "Body");
+    }
+
+    #[test]
+    fn parse_frontmatter_keeps_comment_lines_inside_default_args_block() {
+        let content =
+            "---\ndefault-args:\n  # pinned for CI\n  BRANCH: main\n  # REGION: eu\n---\nBody";
+        let parsed = parse_frontmatter(content);
+        assert_eq!(
+            parsed.comments,
+            vec![
+                (2, "  # pinned for CI".to_string()),
+                (4, "  # REGION: eu".to_string()),
+            ]
+        );
+        assert_eq!(
+            parsed.default_args,
+            Some(HashMap::from([("BRANCH".to_string(), "main".to_string())]))
+        );
+        assert_eq!(parsed.body, "Body");
     }
 }
@@ -1,6 +1,7 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use ts_rs::TS;
 
@@ -17,4 +18,5 @@ pub struct CustomPrompt {
     pub content: String,
     pub description: Option<String>,
     pub argument_hint: Option<String>,
+    pub default_args: Option<HashMap<String, String>>,
 }
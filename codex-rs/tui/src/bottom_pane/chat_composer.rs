@@ -174,12 +174,12 @@ use super::skill_popup::MentionItem;
 use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::CompiledPrompt;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::expand_named_placeholder_defaults;
 use crate::bottom_pane::prompt_args::parse_slash_name;
-use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
-use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -372,6 +372,8 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    /// `custom_prompts` with their placeholders scanned, reused on every expansion.
+    compiled_prompts: Vec<CompiledPrompt>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     remote_image_urls: Vec<String>,
@@ -481,6 +483,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            compiled_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             remote_image_urls: Vec::new(),
@@ -1376,7 +1379,11 @@ impl ChatComposer {
                             }
                         }
                         CommandItem::UserPrompt(idx) => {
-                            if let Some(prompt) = popup.prompt(idx) {
+                            if let Some(prompt) = popup.prompt(idx).and_then(|prompt| {
+                                self.compiled_prompts
+                                    .iter()
+                                    .find(|compiled| compiled.prompt().name == prompt.name)
+                            }) {
                                 match prompt_selection_action(
                                     prompt,
                                     first_line,
@@ -1419,7 +1426,10 @@ impl ChatComposer {
                 let first_line = text.lines().next().unwrap_or("");
                 if let Some((name, _rest, _rest_offset)) = parse_slash_name(first_line)
                     && let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:"))
-                    && let Some(prompt) = self.custom_prompts.iter().find(|p| p.name == prompt_name)
+                    && let Some(prompt) = self
+                        .compiled_prompts
+                        .iter()
+                        .find(|p| p.prompt().name == prompt_name)
                     && let Some(expanded) =
                         expand_if_numeric_with_positional_args(prompt, first_line, &text_elements)
                 {
@@ -1445,7 +1455,11 @@ impl ChatComposer {
                             return (InputResult::Command(cmd), true);
                         }
                         CommandItem::UserPrompt(idx) => {
-                            if let Some(prompt) = popup.prompt(idx) {
+                            if let Some(prompt) = popup.prompt(idx).and_then(|prompt| {
+                                self.compiled_prompts
+                                    .iter()
+                                    .find(|compiled| compiled.prompt().name == prompt.name)
+                            }) {
                                 match prompt_selection_action(
                                     prompt,
                                     first_line,
@@ -2304,7 +2318,7 @@ impl ChatComposer {
 
         if self.slash_commands_enabled() {
             let expanded_prompt =
-                match expand_custom_prompt(&text, &text_elements, &self.custom_prompts) {
+                match expand_custom_prompt(&text, &text_elements, &self.compiled_prompts) {
                     Ok(expanded) => expanded,
                     Err(err) => {
                         self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
@@ -3480,6 +3494,7 @@ impl ChatComposer {
     }
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.custom_prompts = prompts.clone();
+        self.compiled_prompts = prompts.iter().cloned().map(CompiledPrompt::new).collect();
         if let ActivePopup::Command(popup) = &mut self.active_popup {
             popup.set_prompts(prompts);
         }
@@ -4361,19 +4376,19 @@ impl ChatComposer {
 }
 
 fn prompt_selection_action(
-    prompt: &CustomPrompt,
+    compiled: &CompiledPrompt,
     first_line: &str,
     mode: PromptSelectionMode,
     text_elements: &[TextElement],
 ) -> PromptSelectionAction {
-    let named_args = prompt_argument_names(&prompt.content);
-    let has_numeric = prompt_has_numeric_placeholders(&prompt.content);
+    let prompt = compiled.prompt();
+    let named_args = compiled.required();
+    let has_numeric = compiled.has_numeric_placeholders();
 
     match mode {
         PromptSelectionMode::Completion => {
             if !named_args.is_empty() {
-                let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                let (text, cursor) = prompt_command_with_arg_placeholders(&prompt.name, named_args);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
@@ -4388,8 +4403,7 @@ fn prompt_selection_action(
         }
         PromptSelectionMode::Submit => {
             if !named_args.is_empty() {
-                let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                let (text, cursor) = prompt_command_with_arg_placeholders(&prompt.name, named_args);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
@@ -4397,7 +4411,7 @@ fn prompt_selection_action(
             }
             if has_numeric {
                 if let Some(expanded) =
-                    expand_if_numeric_with_positional_args(prompt, first_line, text_elements)
+                    expand_if_numeric_with_positional_args(compiled, first_line, text_elements)
                 {
                     return PromptSelectionAction::Submit {
                         text: expanded.text,
@@ -4408,7 +4422,8 @@ fn prompt_selection_action(
                 return PromptSelectionAction::Insert { text, cursor: None };
             }
             PromptSelectionAction::Submit {
-                text: prompt.content.clone(),
+                // Only defaulted `${NAME:-default}` placeholders can remain at this point.
+                text: expand_named_placeholder_defaults(compiled),
                 // By now we know this custom prompt has no args, so no text elements to preserve.
                 text_elements: Vec::new(),
             }
@@ -7924,6 +7939,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         type_chars_humanlike(
@@ -7962,6 +7978,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -7997,6 +8014,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8036,6 +8054,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8091,6 +8110,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8147,6 +8167,7 @@ mod tests {
             content: "Review changes".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8196,6 +8217,7 @@ mod tests {
             content: "Please review the following code:\n\n$1".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         // Type the slash command
@@ -8259,6 +8281,7 @@ mod tests {
             content: "Review $IMG\n\n$CODE".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8385,6 +8408,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8435,6 +8459,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         // Provide only one of the required args
@@ -8490,6 +8515,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         // Type the slash command with two args and hit Enter to submit.
@@ -8528,6 +8554,7 @@ mod tests {
             content: "Hello".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer.attach_image(PathBuf::from("/tmp/unused.png"));
@@ -8566,6 +8593,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         type_chars_humanlike(
@@ -8609,6 +8637,7 @@ mod tests {
             content: "Echo: $1".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8649,6 +8678,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         composer
@@ -8691,6 +8721,7 @@ mod tests {
             content: "Echo: $1".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         let oversized_arg = "x".repeat(MAX_USER_INPUT_TEXT_CHARS);
@@ -8733,10 +8764,11 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         };
 
         let action = prompt_selection_action(
-            &prompt,
+            &CompiledPrompt::new(prompt),
             "/prompts:my-prompt foo bar",
             PromptSelectionMode::Submit,
             &[],
@@ -8773,6 +8805,7 @@ mod tests {
             content: "Echo: $ARGUMENTS".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         // Type positional args; should submit with numeric expansion, no errors.
@@ -8809,6 +8842,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         type_chars_humanlike(
@@ -8845,6 +8879,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         type_chars_humanlike(
@@ -8883,6 +8918,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }]);
 
         type_chars_humanlike(
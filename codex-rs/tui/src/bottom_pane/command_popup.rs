@@ -354,6 +354,7 @@ mod tests {
                 content: "hello from foo".to_string(),
                 description: None,
                 argument_hint: None,
+                default_args: None,
             },
             CustomPrompt {
                 name: "bar".to_string(),
@@ -361,6 +362,7 @@ mod tests {
                 content: "hello from bar".to_string(),
                 description: None,
                 argument_hint: None,
+                default_args: None,
             },
         ];
         let popup = CommandPopup::new(prompts, CommandPopupFlags::default());
@@ -386,6 +388,7 @@ mod tests {
                 content: "should be ignored".to_string(),
                 description: None,
                 argument_hint: None,
+                default_args: None,
             }],
             CommandPopupFlags::default(),
         );
@@ -409,6 +412,7 @@ mod tests {
                 content: "body".to_string(),
                 description: Some("Create feature branch, commit and open draft PR.".to_string()),
                 argument_hint: None,
+                default_args: None,
             }],
             CommandPopupFlags::default(),
         );
@@ -429,6 +433,7 @@ mod tests {
                 content: "body".to_string(),
                 description: None,
                 argument_hint: None,
+                default_args: None,
             }],
             CommandPopupFlags::default(),
         );
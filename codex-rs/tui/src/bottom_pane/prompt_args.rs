@@ -6,11 +6,13 @@ use lazy_static::lazy_static;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
-use std::collections::HashSet;
+use std::ops::Range;
 
 lazy_static! {
     static ref PROMPT_ARG_REGEX: Regex =
         Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
+    static ref PROMPT_ARG_WITH_DEFAULT_REGEX: Regex =
+        Regex::new(r"\$\{[A-Z][A-Z0-9_]*:-[^}]*\}").unwrap_or_else(|_| std::process::abort());
 }
 
 #[derive(Debug)]
@@ -85,6 +87,38 @@ pub fn parse_slash_name(line: &str) -> Option<(&str, &str, usize)> {
     Some((name, rest, rest_offset))
 }
 
+/// A slash-command invocation taken from the first line of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)] // Not used by the composer yet; only built by `split_invocation`.
+pub struct InvocationLine {
+    /// Command name without the leading `/`.
+    pub name: String,
+    /// Arguments following the name on the same line, with leading whitespace trimmed.
+    pub rest: String,
+}
+
+/// Split `text` into a leading slash-command line and the trailing prose.
+///
+/// Only the first line is considered for the invocation (via `parse_slash_name`);
+/// everything after the first newline is returned unchanged as the prose so the
+/// caller can append it to the expanded command. When the first line is not a
+/// slash command, returns `(None, text)`.
+#[allow(dead_code)] // No caller in the composer yet; kept for hosts that append the prose.
+pub fn split_invocation(text: &str) -> (Option<InvocationLine>, String) {
+    let (first_line, prose) = match text.split_once('\n') {
+        Some((first, prose)) => (first.strip_suffix('\r').unwrap_or(first), prose),
+        None => (text, ""),
+    };
+    let Some((name, rest, _rest_offset)) = parse_slash_name(first_line) else {
+        return (None, text.to_string());
+    };
+    let invocation = InvocationLine {
+        name: name.to_string(),
+        rest: rest.trim_end().to_string(),
+    };
+    (Some(invocation), prose.to_string())
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PromptArg {
     pub text: String,
@@ -97,6 +131,33 @@ pub struct PromptExpansion {
     pub text_elements: Vec<TextElement>,
 }
 
+/// A value for a named placeholder: plain text collected by a form, or a
+/// [`PromptArg`] parsed from the composer that also carries text elements.
+pub trait PromptInput {
+    fn text(&self) -> &str;
+    fn text_elements(&self) -> &[TextElement];
+}
+
+impl PromptInput for String {
+    fn text(&self) -> &str {
+        self
+    }
+
+    fn text_elements(&self) -> &[TextElement] {
+        &[]
+    }
+}
+
+impl PromptInput for PromptArg {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn text_elements(&self) -> &[TextElement] {
+        &self.text_elements
+    }
+}
+
 /// Parse positional arguments using shlex semantics (supports quoted tokens).
 ///
 /// `text_elements` must be relative to `rest`.
@@ -104,26 +165,261 @@ pub fn parse_positional_args(rest: &str, text_elements: &[TextElement]) -> Vec<P
     parse_tokens_with_elements(rest, text_elements)
 }
 
+/// Count the positional arguments in `rest`, as `$#` would expand them.
+#[allow(dead_code)] // No caller in the composer yet; kept for hosts that branch on it.
+pub fn positional_count(rest: &str) -> usize {
+    parse_positional_args(rest, &[]).len()
+}
+
+/// A named placeholder occurrence in a prompt template. Names and defaults are
+/// byte ranges into the scanned content, so a [`CompiledPrompt`] can keep them
+/// next to the prompt they point into.
+#[derive(Debug, Clone)]
+struct NamedPlaceholder {
+    /// Byte offset of the leading `$`.
+    start: usize,
+    /// Byte offset just past the placeholder (including any closing brace).
+    end: usize,
+    name: Range<usize>,
+    /// Fallback from the braced `${NAME:-default}` form; `None` for bare `$NAME`.
+    default: Option<Range<usize>>,
+    /// Preceded by another `$` (`$$NAME`) or an odd number of backslashes
+    /// (`\$NAME`), so it stays literal.
+    escaped: bool,
+    /// Backslashes immediately before the `$`. Each pair collapses to one literal
+    /// backslash and an odd one out is the escape, which is dropped on expansion.
+    backslashes: usize,
+}
+
+/// Scan `content` for bare `$NAME` and braced `${NAME:-default}` placeholders,
+/// ordered by position. `$ARGUMENTS` is excluded since it is positional.
+fn named_placeholders(content: &str) -> Vec<NamedPlaceholder> {
+    let is_escaped = |start: usize| {
+        (start > 0 && content.as_bytes()[start - 1] == b'$')
+            || backslashes_before(content, start) % 2 == 1
+    };
+    let mut found: Vec<NamedPlaceholder> = PROMPT_ARG_REGEX
+        .find_iter(content)
+        .map(|m| NamedPlaceholder {
+            start: m.start(),
+            end: m.end(),
+            name: m.start() + 1..m.end(),
+            default: None,
+            escaped: is_escaped(m.start()),
+            backslashes: backslashes_before(content, m.start()),
+        })
+        .collect();
+    found.extend(PROMPT_ARG_WITH_DEFAULT_REGEX.find_iter(content).map(|m| {
+        // Strip the surrounding `${` and `}`; the regex guarantees the `:-` separator.
+        let inner_start = m.start() + 2;
+        let inner_end = m.end() - 1;
+        let name_end = content[inner_start..inner_end]
+            .find(":-")
+            .map_or(inner_end, |sep| inner_start + sep);
+        NamedPlaceholder {
+            start: m.start(),
+            end: m.end(),
+            name: inner_start..name_end,
+            default: Some((name_end + ":-".len()).min(inner_end)..inner_end),
+            escaped: is_escaped(m.start()),
+            backslashes: backslashes_before(content, m.start()),
+        }
+    }));
+    found.sort_by_key(|placeholder| placeholder.start);
+
+    // Drop bare matches nested inside a braced default (e.g. `${A:-$B}`).
+    let mut placeholders: Vec<NamedPlaceholder> = Vec::with_capacity(found.len());
+    for placeholder in found {
+        if placeholders
+            .last()
+            .is_some_and(|prev| placeholder.start < prev.end)
+        {
+            continue;
+        }
+        placeholders.push(placeholder);
+    }
+    placeholders.retain(|placeholder| &content[placeholder.name.clone()] != "ARGUMENTS");
+    placeholders
+}
+
+/// Count the backslashes immediately before byte offset `index` of `content`.
+fn backslashes_before(content: &str, index: usize) -> usize {
+    let prefix = &content[..index];
+    prefix.len() - prefix.trim_end_matches('\\').len()
+}
+
+/// A named argument declared by a prompt template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptArgSpec {
+    /// Variable name without the leading `$`.
+    pub name: String,
+    /// Fallback from the first `${NAME:-default}` occurrence, if any.
+    pub default: Option<String>,
+    /// `true` when the name appears at least once as a bare `$NAME`.
+    pub required: bool,
+}
+
+/// Extracts the named argument specs from a prompt template, de-duplicated and
+/// in the order of first appearance.
+///
+/// Both bare `$NAME` and braced `${NAME:-default}` placeholders are reported;
+/// escaped `$$NAME` / `\$NAME` forms and `$ARGUMENTS` are ignored.
+pub fn prompt_arg_specs(content: &str) -> Vec<PromptArgSpec> {
+    arg_specs_from_placeholders(content, &named_placeholders(content))
+}
+
+/// Fold the scanned `placeholders` of `content` into [`PromptArgSpec`]s in a
+/// single pass.
+fn arg_specs_from_placeholders(
+    content: &str,
+    placeholders: &[NamedPlaceholder],
+) -> Vec<PromptArgSpec> {
+    let mut specs: Vec<PromptArgSpec> = Vec::new();
+    let mut index_by_name: HashMap<&str, usize> = HashMap::new();
+    for placeholder in placeholders
+        .iter()
+        .filter(|placeholder| !placeholder.escaped)
+    {
+        let name = &content[placeholder.name.clone()];
+        let idx = *index_by_name.entry(name).or_insert_with(|| {
+            specs.push(PromptArgSpec {
+                name: name.to_string(),
+                default: None,
+                required: false,
+            });
+            specs.len() - 1
+        });
+        let spec = &mut specs[idx];
+        match &placeholder.default {
+            Some(default) => {
+                spec.default
+                    .get_or_insert_with(|| content[default.clone()].to_string());
+            }
+            None => spec.required = true,
+        }
+    }
+    specs
+}
+
 /// Extracts the unique placeholder variable names from a prompt template.
 ///
 /// A placeholder is any token that matches the pattern `$[A-Z][A-Z0-9_]*`
 /// (for example `$USER`). The function returns the variable names without
 /// the leading `$`, de-duplicated and in the order of first appearance.
+///
+/// Braced placeholders with a default (`${BRANCH:-main}`) are optional and are
+/// not returned unless the same name also appears in the bare form.
 pub fn prompt_argument_names(content: &str) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut names = Vec::new();
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        if m.start() > 0 && content.as_bytes()[m.start() - 1] == b'$' {
-            continue;
-        }
-        let name = &content[m.start() + 1..m.end()];
-        // Exclude special positional aggregate token from named args.
-        if name == "ARGUMENTS" {
-            continue;
-        }
-        let name = name.to_string();
-        if seen.insert(name.clone()) {
-            names.push(name);
+    prompt_arg_specs(content)
+        .into_iter()
+        .filter(|spec| spec.required)
+        .map(|spec| spec.name)
+        .collect()
+}
+
+/// Names the user must supply for `prompt`: the required placeholders from
+/// [`prompt_argument_names`] that have no `default-args` entry in the frontmatter.
+pub fn required_prompt_args(prompt: &CustomPrompt) -> Vec<String> {
+    prompt_argument_names(&prompt.content)
+        .into_iter()
+        .filter(|name| declared_default(prompt, name).is_none())
+        .collect()
+}
+
+fn declared_default<'a>(prompt: &'a CustomPrompt, name: &str) -> Option<&'a str> {
+    prompt.default_args.as_ref()?.get(name).map(String::as_str)
+}
+
+/// One named argument of a saved prompt, as needed to render an input form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)] // Only built by `prompt_arg_manifest`.
+pub struct PromptArgInfo {
+    pub name: String,
+    /// `true` when the name appears as a bare `$NAME` without any default.
+    pub required: bool,
+    /// Text documented for the argument in the prompt's `argument-hint`
+    /// (for example `[USER=<reviewer>]`).
+    pub description: Option<String>,
+    /// Fallback from the `default-args` frontmatter or a `${NAME:-default}`
+    /// placeholder.
+    pub default: Option<String>,
+}
+
+/// Build the named-argument manifest for `prompt`, in order of first appearance.
+///
+/// Names, requiredness and defaults come from `prompt_arg_specs` and the
+/// `default-args` frontmatter; descriptions come from `NAME=...` entries in the
+/// argument hint.
+#[allow(dead_code)] // No input form renders it yet; kept for hosts that build one.
+pub fn prompt_arg_manifest(prompt: &CustomPrompt) -> Vec<PromptArgInfo> {
+    prompt_arg_specs(&prompt.content)
+        .into_iter()
+        .map(|spec| {
+            let declared = declared_default(prompt, &spec.name).map(str::to_string);
+            PromptArgInfo {
+                description: prompt
+                    .argument_hint
+                    .as_deref()
+                    .and_then(|hint| argument_hint_description(hint, &spec.name)),
+                required: spec.required && declared.is_none(),
+                default: declared.or(spec.default),
+                name: spec.name,
+            }
+        })
+        .collect()
+}
+
+/// Find the `NAME=description` entry for `name` in an argument hint such as
+/// `[USER=<the reviewer>] [PR_TITLE="<title>"]`.
+///
+/// Entries are the bracketed groups, so descriptions may contain spaces; a hint
+/// without brackets is split with shlex rules instead. Surrounding quotes are
+/// stripped from the description.
+fn argument_hint_description(hint: &str, name: &str) -> Option<String> {
+    let entries: Vec<String> = if hint.contains('[') {
+        hint.split('[')
+            .skip(1)
+            .map(|group| group.split_once(']').map_or(group, |(entry, _)| entry))
+            .map(str::to_string)
+            .collect()
+    } else {
+        Shlex::new(hint).collect()
+    };
+    entries.iter().find_map(|entry| {
+        let (key, description) = entry.split_once('=')?;
+        let description = description.trim();
+        let description = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| {
+                description
+                    .strip_prefix(quote)
+                    .and_then(|inner| inner.strip_suffix(quote))
+            })
+            .unwrap_or(description);
+        (key.trim() == name && !description.is_empty()).then(|| description.to_string())
+    })
+}
+
+/// Lists escaped placeholders (`$$NAME`, `$${NAME:-x}`, `\$NAME`) whose `NAME` is
+/// also used live elsewhere, bare or with a default.
+///
+/// This is advisory only: the escape still expands to a literal, but when the same
+/// name is expanded elsewhere in the template the escape is often a typo. Names are
+/// returned without the leading `$`, de-duplicated and in order of first appearance.
+#[allow(dead_code)] // Opt-in lint; nothing surfaces its warnings yet.
+pub fn ambiguous_escaped_placeholders(content: &str) -> Vec<String> {
+    let placeholders = named_placeholders(content);
+    let name = |placeholder: &NamedPlaceholder| &content[placeholder.name.clone()];
+    let mut names: Vec<String> = Vec::new();
+    for escaped in placeholders
+        .iter()
+        .filter(|placeholder| placeholder.escaped)
+    {
+        let live = placeholders
+            .iter()
+            .any(|other| !other.escaped && name(other) == name(escaped));
+        if live && !names.iter().any(|known| known == name(escaped)) {
+            names.push(name(escaped).to_string());
         }
     }
     names
@@ -144,8 +440,12 @@ fn shift_text_element_left(elem: &TextElement, offset: usize) -> Option<TextElem
 /// Parses the `key=value` pairs that follow a custom prompt name.
 ///
 /// The input is split using shlex rules, so quoted values are supported
-/// (for example `USER="Alice Smith"`). The function returns a map of parsed
-/// arguments, or an error if a token is missing `=` or if the key is empty.
+/// (for example `USER="Alice Smith"`). GNU-style `--key value` and `--key=value`
+/// tokens are accepted as alternate spellings of `key=value`; flag names are
+/// mapped onto placeholder names, so `--dry-run` sets `DRY_RUN`. The function returns
+/// a map of parsed arguments, or an error if a token is missing `=` (or a `--key`
+/// has no value; a following `KEY=value` token is not taken as one) or if the key
+/// is empty.
 pub fn parse_prompt_inputs(
     rest: &str,
     text_elements: &[TextElement],
@@ -157,23 +457,49 @@ pub fn parse_prompt_inputs(
 
     // Tokenize the rest of the command using shlex rules, but keep text element
     // ranges relative to each emitted token.
-    for token in parse_tokens_with_elements(rest, text_elements) {
-        let Some((key, value)) = token.text.split_once('=') else {
+    let mut tokens = parse_tokens_with_elements(rest, text_elements)
+        .into_iter()
+        .peekable();
+    while let Some(token) = tokens.next() {
+        let (prefix_len, assignment) = match token.text.strip_prefix("--") {
+            Some(flag) => ("--".len(), flag),
+            None => (0, token.text.as_str()),
+        };
+        if prefix_len > 0 && !assignment.contains('=') {
+            if assignment.is_empty() {
+                return Err(PromptArgsError::MissingKey { token: token.text });
+            }
+            // `--key value`: the next token is the value, unless it is another flag
+            // or a `KEY=value` assignment of its own.
+            let Some(value) = tokens.next_if(|next| {
+                !next.text.starts_with("--") && !is_placeholder_assignment(&next.text)
+            }) else {
+                return Err(PromptArgsError::MissingAssignment { token: token.text });
+            };
+            map.insert(flag_placeholder_name(assignment), value);
+            continue;
+        }
+        let Some((key, value)) = assignment.split_once('=') else {
             return Err(PromptArgsError::MissingAssignment { token: token.text });
         };
         if key.is_empty() {
             return Err(PromptArgsError::MissingKey { token: token.text });
         }
-        // The token is `key=value`; translate element ranges into the value-only
-        // coordinate space by subtracting the `key=` prefix length.
-        let value_start = key.len() + 1;
+        // The token is `key=value` (optionally `--`-prefixed); translate element
+        // ranges into the value-only coordinate space by subtracting the prefix length.
+        let value_start = prefix_len + key.len() + 1;
         let value_elements = token
             .text_elements
             .iter()
             .filter_map(|elem| shift_text_element_left(elem, value_start))
             .collect();
+        let key = if prefix_len > 0 {
+            flag_placeholder_name(key)
+        } else {
+            key.to_string()
+        };
         map.insert(
-            key.to_string(),
+            key,
             PromptArg {
                 text: value.to_string(),
                 text_elements: value_elements,
@@ -183,6 +509,91 @@ pub fn parse_prompt_inputs(
     Ok(map)
 }
 
+/// Whether `token` is a `KEY=value` assignment whose key is a placeholder name
+/// (`[A-Z][A-Z0-9_]*`).
+fn is_placeholder_assignment(token: &str) -> bool {
+    token.split_once('=').is_some_and(|(key, _)| {
+        let mut chars = key.chars();
+        chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
+/// Map a `--flag-name` onto the `$FLAG_NAME` placeholder it fills.
+fn flag_placeholder_name(flag: &str) -> String {
+    flag.to_ascii_uppercase().replace('-', "_")
+}
+
+/// A saved prompt with its placeholders scanned once, so repeated expansions
+/// only cost the size of the output. The composer keeps one per saved prompt.
+#[derive(Debug, Clone)]
+pub struct CompiledPrompt {
+    prompt: CustomPrompt,
+    specs: Vec<PromptArgSpec>,
+    required: Vec<String>,
+    has_numeric: bool,
+    placeholders: Vec<NamedPlaceholder>,
+}
+
+impl CompiledPrompt {
+    pub fn new(prompt: CustomPrompt) -> Self {
+        let placeholders = named_placeholders(&prompt.content);
+        let specs = arg_specs_from_placeholders(&prompt.content, &placeholders);
+        // Same result as `required_prompt_args`, derived from the single scan.
+        let required = specs
+            .iter()
+            .filter(|spec| spec.required && declared_default(&prompt, &spec.name).is_none())
+            .map(|spec| spec.name.clone())
+            .collect();
+        let has_numeric = prompt_has_numeric_placeholders(&prompt.content);
+        Self {
+            prompt,
+            specs,
+            required,
+            has_numeric,
+            placeholders,
+        }
+    }
+
+    pub fn prompt(&self) -> &CustomPrompt {
+        &self.prompt
+    }
+
+    /// Names the user must supply, as returned by [`required_prompt_args`].
+    pub fn required(&self) -> &[String] {
+        &self.required
+    }
+
+    /// Whether the prompt uses numeric placeholders, as detected by
+    /// [`prompt_has_numeric_placeholders`].
+    pub fn has_numeric_placeholders(&self) -> bool {
+        self.has_numeric
+    }
+
+    /// Whether the prompt is invoked with positional args: it uses numeric
+    /// placeholders and every named placeholder it has can fall back to a default.
+    fn takes_positional_args(&self) -> bool {
+        self.has_numeric && self.required.is_empty()
+    }
+
+    /// Expand the named placeholders with `inputs`. Omitted names fall back to
+    /// the prompt's `default-args` and then to inline `${NAME:-default}` values;
+    /// callers are expected to have checked [`Self::required`].
+    pub fn expand<V: PromptInput>(&self, inputs: &HashMap<String, V>) -> PromptExpansion {
+        let (text, text_elements) = expand_placeholders_with_elements(
+            &self.prompt.content,
+            &self.placeholders,
+            inputs,
+            self.prompt.default_args.as_ref(),
+            &process_env,
+        );
+        PromptExpansion {
+            text,
+            text_elements,
+        }
+    }
+}
+
 /// Expands a message of the form `/prompts:name [value] [value] …` using a matching saved prompt.
 ///
 /// If the text does not start with `/prompts:`, or if no prompt named `name` exists,
@@ -191,7 +602,7 @@ pub fn parse_prompt_inputs(
 pub fn expand_custom_prompt(
     text: &str,
     text_elements: &[TextElement],
-    custom_prompts: &[CustomPrompt],
+    custom_prompts: &[CompiledPrompt],
 ) -> Result<Option<PromptExpansion>, PromptExpansionError> {
     let Some((name, rest, rest_offset)) = parse_slash_name(text) else {
         return Ok(None);
@@ -202,12 +613,14 @@ pub fn expand_custom_prompt(
         return Ok(None);
     };
 
-    let prompt = match custom_prompts.iter().find(|p| p.name == prompt_name) {
+    let prompt = match custom_prompts
+        .iter()
+        .find(|p| p.prompt().name == prompt_name)
+    {
         Some(prompt) => prompt,
         None => return Ok(None),
     };
     // If there are named placeholders, expect key=value inputs.
-    let required = prompt_argument_names(&prompt.content);
     let local_elements: Vec<TextElement> = text_elements
         .iter()
         .filter_map(|elem| {
@@ -220,53 +633,131 @@ pub fn expand_custom_prompt(
             (shifted.byte_range.start < shifted.byte_range.end).then_some(shifted)
         })
         .collect();
-    if !required.is_empty() {
-        let inputs = parse_prompt_inputs(rest, &local_elements).map_err(|error| {
-            PromptExpansionError::Args {
-                command: format!("/{name}"),
-                error,
-            }
-        })?;
-        let missing: Vec<String> = required
-            .into_iter()
-            .filter(|k| !inputs.contains_key(k))
-            .collect();
-        if !missing.is_empty() {
-            return Err(PromptExpansionError::MissingArgs {
-                command: format!("/{name}"),
-                missing,
-            });
-        }
-        let (text, elements) = expand_named_placeholders_with_elements(&prompt.content, &inputs);
-        return Ok(Some(PromptExpansion {
-            text,
-            text_elements: elements,
-        }));
+    if !prompt.specs.is_empty() && !prompt.takes_positional_args() {
+        let inputs = match positional_named_inputs(prompt, rest, &local_elements) {
+            Some(inputs) => inputs,
+            None => parse_prompt_inputs(rest, &local_elements).map_err(|error| {
+                PromptExpansionError::Args {
+                    command: format!("/{name}"),
+                    error,
+                }
+            })?,
+        };
+        return expand_custom_prompt_with_inputs(prompt, &inputs).map(Some);
     }
 
     // Otherwise, treat it as numeric/positional placeholder prompt (or none).
     let pos_args = parse_positional_args(rest, &local_elements);
-    Ok(Some(expand_numeric_placeholders(
-        &prompt.content,
-        &pos_args,
-    )))
+    Ok(Some(expand_positional_prompt(prompt, &pos_args)))
 }
 
-/// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
-pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
-    if content.contains("$ARGUMENTS") {
-        return true;
+/// Expand `prompt` with positional `args`, filling any defaulted named
+/// placeholders first so `Deploy $1 to ${ENV:-prod}` works with a single arg.
+fn expand_positional_prompt(prompt: &CompiledPrompt, args: &[PromptArg]) -> PromptExpansion {
+    if prompt.placeholders.is_empty() {
+        return expand_numeric_placeholders(&prompt.prompt.content, args);
+    }
+    expand_numeric_placeholders(&expand_named_placeholder_defaults(prompt), args)
+}
+
+/// Checks that `inputs` supplies every named placeholder required by `prompt`
+/// without expanding it.
+///
+/// Returns `PromptExpansionError::MissingArgs` listing the absent names, in the
+/// order they first appear in the prompt.
+#[allow(dead_code)] // No caller in the composer yet; kept for hosts that check input forms.
+pub fn validate_prompt_args(
+    prompt: &CustomPrompt,
+    inputs: &HashMap<String, String>,
+) -> Result<(), PromptExpansionError> {
+    let missing: Vec<String> = required_prompt_args(prompt)
+        .into_iter()
+        .filter(|k| !inputs.contains_key(k))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
     }
-    let bytes = content.as_bytes();
+    Err(PromptExpansionError::MissingArgs {
+        command: format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.name),
+        missing,
+    })
+}
+
+/// Map bare tokens such as `/prompts:review Alice main` onto the prompt's
+/// required names in order of first appearance.
+///
+/// Returns `None` when any token is written as a named input (`KEY=value` or
+/// `--key`) or there are more tokens than names, so the caller reports the usual
+/// `KEY=value` errors for mixed forms.
+fn positional_named_inputs(
+    prompt: &CompiledPrompt,
+    rest: &str,
+    text_elements: &[TextElement],
+) -> Option<HashMap<String, PromptArg>> {
+    let tokens = parse_positional_args(rest, text_elements);
+    // The same names as `prompt_argument_names`, taken from the compiled specs.
+    let names: Vec<&str> = prompt
+        .specs
+        .iter()
+        .filter(|spec| spec.required)
+        .map(|spec| spec.name.as_str())
+        .collect();
+    let named_form = tokens
+        .iter()
+        .any(|token| token.text.contains('=') || token.text.starts_with("--"));
+    if tokens.is_empty() || named_form || tokens.len() > names.len() {
+        return None;
+    }
+    Some(names.into_iter().map(str::to_string).zip(tokens).collect())
+}
+
+/// Expands the named placeholders of `prompt` with inputs that were collected
+/// directly (for example from a form), skipping the `KEY=value` parsing of
+/// [`expand_custom_prompt`] so values are used verbatim. `expand_custom_prompt`
+/// itself lands here once it has parsed the invocation.
+///
+/// Returns `PromptExpansionError::MissingArgs` when a required name is absent.
+pub fn expand_custom_prompt_with_inputs<V: PromptInput>(
+    prompt: &CompiledPrompt,
+    inputs: &HashMap<String, V>,
+) -> Result<PromptExpansion, PromptExpansionError> {
+    let missing: Vec<String> = prompt
+        .required()
+        .iter()
+        .filter(|k| !inputs.contains_key(*k))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.prompt().name),
+            missing,
+        });
+    }
+    Ok(prompt.expand(inputs))
+}
+
+/// Detect whether `content` contains numeric placeholders ($1..$9, optionally
+/// braced with a default as `${1:-x}`) or `$ARGUMENTS` (including its `$@` / `$*`
+/// aliases) or the `$#` argument count.
+///
+/// Tokens are recognized exactly as [`expand_numeric_placeholders`] does, so a
+/// `$` escaped by an odd number of backslashes (`\$1`) or a `$$` pair does not
+/// count, nor does a braced form it would keep literal (such as `${1}`).
+pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     let mut i = 0;
-    while i + 1 < bytes.len() {
-        if bytes[i] == b'$' {
-            let b1 = bytes[i + 1];
-            if (b'1'..=b'9').contains(&b1) {
+    while let Some(off) = content[i..].find('$') {
+        let j = i + off;
+        let rest = &content[j..];
+        if rest[1..].starts_with('$') {
+            i = j + 2;
+        } else if let Some((_, consumed)) = numeric_token(rest) {
+            if backslashes_before(&content[i..], off) % 2 == 0 {
                 return true;
             }
+            i = j + consumed;
+        } else {
+            i = j + 1;
         }
-        i += 1;
     }
     false
 }
@@ -311,62 +802,75 @@ pub fn extract_positional_args_for_prompt_line(
     parse_positional_args(args_str, &local_elements)
 }
 
-/// If the prompt only uses numeric placeholders and the first line contains
-/// positional args for it, expand and return Some(expanded); otherwise None.
+/// If the prompt uses numeric placeholders (and any named ones have defaults) and
+/// the first line contains positional args for it, expand and return
+/// Some(expanded); otherwise None.
 pub fn expand_if_numeric_with_positional_args(
-    prompt: &CustomPrompt,
+    prompt: &CompiledPrompt,
     first_line: &str,
     text_elements: &[TextElement],
 ) -> Option<PromptExpansion> {
-    if !prompt_argument_names(&prompt.content).is_empty() {
-        return None;
-    }
-    if !prompt_has_numeric_placeholders(&prompt.content) {
+    if !prompt.takes_positional_args() {
         return None;
     }
-    let args = extract_positional_args_for_prompt_line(first_line, &prompt.name, text_elements);
+    let args =
+        extract_positional_args_for_prompt_line(first_line, &prompt.prompt().name, text_elements);
     if args.is_empty() {
         return None;
     }
-    Some(expand_numeric_placeholders(&prompt.content, &args))
+    Some(expand_positional_prompt(prompt, &args))
 }
 
-/// Expand `$1..$9` and `$ARGUMENTS` in `content` with values from `args`.
+/// Expand positional placeholders in `content` with values from `args`.
+///
+/// Supported tokens:
+/// - `$1`..`$9`: the matching argument, or nothing when it is missing
+/// - `${N:-default}`: like `$N`, but `default` when the argument is missing
+/// - `$ARGUMENTS` and its shell-style aliases `$@` / `$*`: all arguments joined by spaces
+/// - `${ARGUMENTS:-all}`: all arguments, or `all` when none were supplied
+/// - `${ARGUMENTS|prefix=--|suffix=,}`: each argument wrapped before joining
+/// - `$#`: the number of arguments
+///
+/// `$$` is kept as written. A backslash escapes the `$` of a token (`\$1` stays a
+/// literal `$1`), while `\\$1` is a literal backslash followed by the placeholder.
+/// Backslashes before any other `$` are left untouched.
 pub fn expand_numeric_placeholders(content: &str, args: &[PromptArg]) -> PromptExpansion {
     let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
     let mut i = 0;
     while let Some(off) = content[i..].find('$') {
         let j = i + off;
-        out.push_str(&content[i..j]);
         let rest = &content[j..];
-        let bytes = rest.as_bytes();
-        if bytes.len() >= 2 {
-            match bytes[1] {
-                b'$' => {
-                    out.push_str("$$");
-                    i = j + 2;
-                    continue;
-                }
-                b'1'..=b'9' => {
-                    let idx = (bytes[1] - b'1') as usize;
-                    if let Some(arg) = args.get(idx) {
-                        append_arg_with_elements(&mut out, &mut out_elements, arg);
-                    }
-                    i = j + 2;
-                    continue;
-                }
-                _ => {}
-            }
+        if rest[1..].starts_with('$') {
+            out.push_str(&content[i..j + 2]);
+            i = j + 2;
+            continue;
         }
-        if rest.len() > "ARGUMENTS".len() && rest[1..].starts_with("ARGUMENTS") {
-            if !args.is_empty() {
-                append_joined_args_with_elements(&mut out, &mut out_elements, args);
+        if let Some((token, consumed)) = numeric_token(rest) {
+            // Backslashes only act as escapes in front of a token.
+            let backslashes = backslashes_before(&content[i..], off);
+            out.push_str(&content[i..j - backslashes]);
+            out.push_str(&"\\".repeat(backslashes / 2));
+            if backslashes % 2 == 1 {
+                out.push_str(&rest[..consumed]);
+                i = j + consumed;
+                continue;
             }
-            i = j + 1 + "ARGUMENTS".len();
+            match token {
+                NumericToken::Arg { index, default } => match (args.get(index), default) {
+                    (Some(arg), _) => append_arg_with_elements(&mut out, &mut out_elements, arg),
+                    (None, Some(default)) => out.push_str(default),
+                    (None, None) => {}
+                },
+                NumericToken::Arguments(format) => {
+                    append_formatted_args_with_elements(&mut out, &mut out_elements, args, &format);
+                }
+                NumericToken::Count => out.push_str(&args.len().to_string()),
+            }
+            i = j + consumed;
             continue;
         }
-        out.push('$');
+        out.push_str(&content[i..=j]);
         i = j + 1;
     }
     out.push_str(&content[i..]);
@@ -376,6 +880,111 @@ pub fn expand_numeric_placeholders(content: &str, args: &[PromptArg]) -> PromptE
     }
 }
 
+/// A positional token recognized by [`expand_numeric_placeholders`].
+enum NumericToken<'a> {
+    /// `$1`..`$9`, or `${N:-default}` with its fallback (zero-based index).
+    Arg {
+        index: usize,
+        default: Option<&'a str>,
+    },
+    /// `$ARGUMENTS`, its `$@` / `$*` aliases and the braced `${ARGUMENTS...}` forms.
+    Arguments(ArgumentsFormat<'a>),
+    /// `$#`.
+    Count,
+}
+
+/// Recognize the positional token at the start of `rest`, which begins with `$`,
+/// returning it and the byte length consumed.
+fn numeric_token(rest: &str) -> Option<(NumericToken<'_>, usize)> {
+    match rest.as_bytes().get(1)? {
+        digit @ b'1'..=b'9' => {
+            let index = (digit - b'1') as usize;
+            return Some((
+                NumericToken::Arg {
+                    index,
+                    default: None,
+                },
+                2,
+            ));
+        }
+        b'@' | b'*' => return Some((NumericToken::Arguments(ArgumentsFormat::default()), 2)),
+        b'#' => return Some((NumericToken::Count, 2)),
+        _ => {}
+    }
+    if let Some((index, default, consumed)) = parse_braced_positional(rest) {
+        let default = Some(default);
+        return Some((NumericToken::Arg { index, default }, consumed));
+    }
+    if let Some((format, consumed)) = parse_braced_arguments(rest) {
+        return Some((NumericToken::Arguments(format), consumed));
+    }
+    rest[1..].starts_with("ARGUMENTS").then(|| {
+        let format = ArgumentsFormat::default();
+        (NumericToken::Arguments(format), 1 + "ARGUMENTS".len())
+    })
+}
+
+/// Parse a braced `${N:-default}` placeholder (`N` in 1..9) at the start of
+/// `rest`, returning the zero-based argument index, the default and the byte
+/// length consumed.
+fn parse_braced_positional(rest: &str) -> Option<(usize, &str, usize)> {
+    let bytes = rest.as_bytes();
+    let digit = *bytes.get(2)?;
+    if !rest.starts_with("${") || !(b'1'..=b'9').contains(&digit) {
+        return None;
+    }
+    let default_start = 3 + ":-".len();
+    if !rest[3..].starts_with(":-") {
+        return None;
+    }
+    let close = default_start + rest[default_start..].find('}')?;
+    Some((
+        (digit - b'1') as usize,
+        &rest[default_start..close],
+        close + 1,
+    ))
+}
+
+/// Options accepted by the braced `${ARGUMENTS...}` form.
+#[derive(Debug, Default, PartialEq)]
+struct ArgumentsFormat<'a> {
+    /// Fallback text used when no arguments were supplied (`:-default`).
+    default: Option<&'a str>,
+    prefix: &'a str,
+    suffix: &'a str,
+}
+
+/// Parse a braced `${ARGUMENTS}` / `${ARGUMENTS:-default}` /
+/// `${ARGUMENTS|prefix=..|suffix=..}` placeholder at the start of `rest`, returning
+/// the format and the byte length consumed.
+///
+/// Unknown modifiers or a missing closing brace yield `None` so the text is kept literal.
+fn parse_braced_arguments(rest: &str) -> Option<(ArgumentsFormat<'_>, usize)> {
+    let modifiers_start = "${ARGUMENTS".len();
+    if !rest.starts_with("${ARGUMENTS") {
+        return None;
+    }
+    let close = rest.find('}')?;
+    let mut format = ArgumentsFormat::default();
+    let mut modifiers = &rest[modifiers_start..close];
+    if let Some(after) = modifiers.strip_prefix(":-") {
+        let (default, remaining) = after.split_at(after.find('|').unwrap_or(after.len()));
+        format.default = Some(default);
+        modifiers = remaining;
+    }
+    if !modifiers.is_empty() {
+        let modifiers = modifiers.strip_prefix('|')?;
+        for modifier in modifiers.split('|') {
+            match modifier.split_once('=') {
+                Some(("prefix", value)) => format.prefix = value,
+                Some(("suffix", value)) => format.suffix = value,
+                _ => return None,
+            }
+        }
+    }
+    Some((format, close + 1))
+}
+
 fn parse_tokens_with_elements(rest: &str, text_elements: &[TextElement]) -> Vec<PromptArg> {
     let mut elements = text_elements.to_vec();
     elements.sort_by_key(|elem| elem.byte_range.start);
@@ -485,26 +1094,64 @@ fn next_replacement<'a>(
     best
 }
 
-fn expand_named_placeholders_with_elements(
+/// Expand named placeholders in `prompt` without any user inputs, so only
+/// placeholders with a `default-args` entry or a `${NAME:-default}` fallback
+/// change (to their defaults).
+pub fn expand_named_placeholder_defaults(prompt: &CompiledPrompt) -> String {
+    prompt.expand(&HashMap::<String, String>::new()).text
+}
+
+fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Resolve the fallback of a `${NAME:-default}` placeholder.
+///
+/// A default of the form `$env.VAR` reads `VAR` through `env` (empty when unset);
+/// any other default is used literally.
+fn resolve_placeholder_default(default: &str, env: &dyn Fn(&str) -> Option<String>) -> String {
+    match default.strip_prefix("$env.") {
+        Some(var) if !var.is_empty() => env(var).unwrap_or_default(),
+        _ => default.to_string(),
+    }
+}
+
+/// Expand the pre-scanned `placeholders` of `content`.
+///
+/// Each name takes its value from `args`, then from the `declared` defaults
+/// (`default-args` frontmatter), then from an inline `${NAME:-default}` fallback.
+fn expand_placeholders_with_elements<V: PromptInput>(
     content: &str,
-    args: &HashMap<String, PromptArg>,
+    placeholders: &[NamedPlaceholder],
+    args: &HashMap<String, V>,
+    declared: Option<&HashMap<String, String>>,
+    env: &dyn Fn(&str) -> Option<String>,
 ) -> (String, Vec<TextElement>) {
     let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
     let mut cursor = 0;
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        let start = m.start();
-        let end = m.end();
-        if start > 0 && content.as_bytes()[start - 1] == b'$' {
-            out.push_str(&content[cursor..end]);
-            cursor = end;
+    for placeholder in placeholders {
+        let NamedPlaceholder {
+            start,
+            end,
+            escaped,
+            backslashes,
+            ..
+        } = *placeholder;
+        let name = &content[placeholder.name.clone()];
+        out.push_str(&content[cursor..start - backslashes]);
+        out.push_str(&"\\".repeat(backslashes / 2));
+        cursor = end;
+        if escaped {
+            out.push_str(&content[start..end]);
             continue;
         }
-        out.push_str(&content[cursor..start]);
-        cursor = end;
-        let key = &content[start + 1..end];
-        if let Some(arg) = args.get(key) {
+        if let Some(arg) = args.get(name) {
             append_arg_with_elements(&mut out, &mut out_elements, arg);
+        } else if let Some(value) = declared.and_then(|declared| declared.get(name)) {
+            out.push_str(value);
+        } else if let Some(default) = &placeholder.default {
+            out.push_str(&resolve_placeholder_default(&content[default.clone()], env));
         } else {
             out.push_str(&content[start..end]);
         }
@@ -516,14 +1163,14 @@ fn expand_named_placeholders_with_elements(
 fn append_arg_with_elements(
     out: &mut String,
     out_elements: &mut Vec<TextElement>,
-    arg: &PromptArg,
+    arg: &impl PromptInput,
 ) {
     let start = out.len();
-    out.push_str(&arg.text);
-    if arg.text_elements.is_empty() {
+    out.push_str(arg.text());
+    if arg.text_elements().is_empty() {
         return;
     }
-    out_elements.extend(arg.text_elements.iter().map(|elem| {
+    out_elements.extend(arg.text_elements().iter().map(|elem| {
         elem.map_range(|range| ByteRange {
             start: start + range.start,
             end: start + range.end,
@@ -531,17 +1178,26 @@ fn append_arg_with_elements(
     }));
 }
 
-fn append_joined_args_with_elements(
+fn append_formatted_args_with_elements(
     out: &mut String,
     out_elements: &mut Vec<TextElement>,
     args: &[PromptArg],
+    format: &ArgumentsFormat<'_>,
 ) {
+    if args.is_empty()
+        && let Some(default) = format.default
+    {
+        out.push_str(default);
+        return;
+    }
     // `$ARGUMENTS` joins args with single spaces while preserving element ranges.
     for (idx, arg) in args.iter().enumerate() {
         if idx > 0 {
             out.push(' ');
         }
+        out.push_str(format.prefix);
         append_arg_with_elements(out, out_elements, arg);
+        out.push_str(format.suffix);
     }
 }
 
@@ -564,6 +1220,10 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    fn compile(prompts: &[CustomPrompt]) -> Vec<CompiledPrompt> {
+        prompts.iter().cloned().map(CompiledPrompt::new).collect()
+    }
+
     #[test]
     fn expand_arguments_basic() {
         let prompts = vec![CustomPrompt {
@@ -572,10 +1232,15 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }];
 
-        let out = expand_custom_prompt("/prompts:my-prompt USER=Alice BRANCH=main", &[], &prompts)
-            .unwrap();
+        let out = expand_custom_prompt(
+            "/prompts:my-prompt USER=Alice BRANCH=main",
+            &[],
+            &compile(&prompts),
+        )
+        .unwrap();
         assert_eq!(
             out,
             Some(PromptExpansion {
@@ -593,12 +1258,13 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }];
 
         let out = expand_custom_prompt(
             "/prompts:my-prompt USER=\"Alice Smith\" BRANCH=dev-main",
             &[],
-            &prompts,
+            &compile(&prompts),
         )
         .unwrap();
         assert_eq!(
@@ -610,6 +1276,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn positional_tokens_fill_named_placeholders_in_order() {
+        let prompts = vec![CustomPrompt {
+            name: "review".to_string(),
+            path: "/tmp/review.md".to_string().into(),
+            content: "Review $USER on $BRANCH".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+        let expand = |text: &str| {
+            expand_custom_prompt(text, &[], &compile(&prompts))
+                .map(|expansion| expansion.map(|e| e.text))
+        };
+
+        assert_eq!(
+            expand("/prompts:review \"Alice Smith\" main").unwrap(),
+            Some("Review Alice Smith on main".to_string())
+        );
+        assert_eq!(
+            expand("/prompts:review BRANCH=main USER=Alice").unwrap(),
+            Some("Review Alice on main".to_string())
+        );
+        let err = expand("/prompts:review Alice BRANCH=main")
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("expected key=value"));
+    }
+
     #[test]
     fn invalid_arg_token_reports_error() {
         let prompts = vec![CustomPrompt {
@@ -618,10 +1313,15 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }];
-        let err = expand_custom_prompt("/prompts:my-prompt USER=Alice stray", &[], &prompts)
-            .unwrap_err()
-            .user_message();
+        let err = expand_custom_prompt(
+            "/prompts:my-prompt USER=Alice stray",
+            &[],
+            &compile(&prompts),
+        )
+        .unwrap_err()
+        .user_message();
         assert!(err.contains("expected key=value"));
     }
 
@@ -633,8 +1333,9 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }];
-        let err = expand_custom_prompt("/prompts:my-prompt USER=Alice", &[], &prompts)
+        let err = expand_custom_prompt("/prompts:my-prompt USER=Alice", &[], &compile(&prompts))
             .unwrap_err()
             .user_message();
         assert!(err.to_lowercase().contains("missing required args"));
@@ -661,9 +1362,10 @@ mod tests {
             content: "literal $$USER".to_string(),
             description: None,
             argument_hint: None,
+            default_args: None,
         }];
 
-        let out = expand_custom_prompt("/prompts:my-prompt", &[], &prompts).unwrap();
+        let out = expand_custom_prompt("/prompts:my-prompt", &[], &compile(&prompts)).unwrap();
         assert_eq!(
             out,
             Some(PromptExpansion {
@@ -851,4 +1553,605 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn split_invocation_separates_command_line_from_prose() {
+        let (invocation, prose) =
+            split_invocation("/deploy prod\nPlease also run tests\nand report back");
+        assert_eq!(
+            invocation,
+            Some(InvocationLine {
+                name: "deploy".to_string(),
+                rest: "prod".to_string(),
+            })
+        );
+        assert_eq!(prose, "Please also run tests\nand report back");
+    }
+
+    #[test]
+    fn split_invocation_without_slash_returns_full_text() {
+        let (invocation, prose) = split_invocation("hello\n/deploy prod");
+        assert_eq!(invocation, None);
+        assert_eq!(prose, "hello\n/deploy prod");
+    }
+
+    #[test]
+    fn escaped_placeholder_matching_live_name_is_flagged() {
+        assert_eq!(
+            ambiguous_escaped_placeholders("Hi $USER, literal $$USER and $$HOME"),
+            vec!["USER".to_string()]
+        );
+        assert_eq!(
+            ambiguous_escaped_placeholders("literal $$USER only"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn escaped_placeholder_matching_defaulted_name_is_flagged() {
+        assert_eq!(
+            ambiguous_escaped_placeholders("On ${BRANCH:-main}, literal $$BRANCH"),
+            vec!["BRANCH".to_string()]
+        );
+        assert_eq!(
+            ambiguous_escaped_placeholders("Hi $USER, literal $${USER:-x} and $${TEAM:-y}"),
+            vec!["USER".to_string()]
+        );
+    }
+
+    #[test]
+    fn braced_arguments_apply_prefix_and_suffix_to_each_arg() {
+        let args: Vec<PromptArg> = ["a", "b", "c"]
+            .into_iter()
+            .map(|text| PromptArg {
+                text: text.to_string(),
+                text_elements: Vec::new(),
+            })
+            .collect();
+
+        let prefixed = expand_numeric_placeholders("run ${ARGUMENTS|prefix=--}", &args);
+        assert_eq!(prefixed.text, "run --a --b --c");
+
+        let suffixed = expand_numeric_placeholders("[${ARGUMENTS|suffix=,}]", &args);
+        assert_eq!(suffixed.text, "[a, b, c,]");
+
+        let empty = expand_numeric_placeholders("run ${ARGUMENTS|prefix=--}", &[]);
+        assert_eq!(empty.text, "run ");
+    }
+
+    #[test]
+    fn braced_arguments_default_applies_only_without_args() {
+        let args = vec![
+            PromptArg {
+                text: "api".to_string(),
+                text_elements: Vec::new(),
+            },
+            PromptArg {
+                text: "web".to_string(),
+                text_elements: Vec::new(),
+            },
+        ];
+
+        let with_args = expand_numeric_placeholders("Test ${ARGUMENTS:-all} now", &args);
+        assert_eq!(with_args.text, "Test api web now");
+
+        let without_args = expand_numeric_placeholders("Test ${ARGUMENTS:-all} now", &[]);
+        assert_eq!(without_args.text, "Test all now");
+
+        let escaped = expand_numeric_placeholders("Literal $${ARGUMENTS:-all}", &args);
+        assert_eq!(escaped.text, "Literal $${ARGUMENTS:-all}");
+    }
+
+    #[test]
+    fn validate_prompt_args_reports_missing_names() {
+        let prompt = CustomPrompt {
+            name: "my-prompt".to_string(),
+            path: "/tmp/my-prompt.md".to_string().into(),
+            content: "Review $USER changes on $BRANCH".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        };
+
+        let complete = HashMap::from([
+            ("USER".to_string(), "Alice".to_string()),
+            ("BRANCH".to_string(), "main".to_string()),
+        ]);
+        assert!(validate_prompt_args(&prompt, &complete).is_ok());
+
+        let incomplete = HashMap::from([("USER".to_string(), "Alice".to_string())]);
+        let err = validate_prompt_args(&prompt, &incomplete).unwrap_err();
+        let PromptExpansionError::MissingArgs { command, missing } = err else {
+            panic!("expected MissingArgs, got {err:?}");
+        };
+        assert_eq!(command, "/prompts:my-prompt");
+        assert_eq!(missing, vec!["BRANCH".to_string()]);
+    }
+
+    #[test]
+    fn braced_named_placeholder_uses_value_or_default() {
+        let prompts = vec![CustomPrompt {
+            name: "my-prompt".to_string(),
+            path: "/tmp/my-prompt.md".to_string().into(),
+            content: "Review $USER on ${BRANCH:-main}".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+        assert_eq!(
+            prompt_argument_names(&prompts[0].content),
+            vec!["USER".to_string()]
+        );
+
+        let provided = expand_custom_prompt(
+            "/prompts:my-prompt USER=Alice BRANCH=dev",
+            &[],
+            &compile(&prompts),
+        )
+        .unwrap();
+        assert_eq!(
+            provided.map(|expansion| expansion.text),
+            Some("Review Alice on dev".to_string())
+        );
+
+        let defaulted =
+            expand_custom_prompt("/prompts:my-prompt USER=Alice", &[], &compile(&prompts)).unwrap();
+        assert_eq!(
+            defaulted.map(|expansion| expansion.text),
+            Some("Review Alice on main".to_string())
+        );
+    }
+
+    #[test]
+    fn escaped_braced_named_placeholder_remains_literal() {
+        let content = "literal $${X:-y} and ${X:-z}";
+        let prompt = CustomPrompt {
+            name: "literal".to_string(),
+            path: "/tmp/literal.md".to_string().into(),
+            content: content.to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        };
+        assert_eq!(prompt_argument_names(content), Vec::<String>::new());
+        assert_eq!(
+            expand_named_placeholder_defaults(&CompiledPrompt::new(prompt)),
+            "literal $${X:-y} and z"
+        );
+    }
+
+    #[test]
+    fn positional_args_expand_prompt_with_defaulted_named_placeholders() {
+        let prompts = vec![CustomPrompt {
+            name: "deploy".to_string(),
+            path: "/tmp/deploy.md".to_string().into(),
+            content: "Deploy $1 to ${ENV:-prod}".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+
+        let out = expand_custom_prompt("/prompts:deploy api", &[], &compile(&prompts))
+            .unwrap()
+            .map(|expansion| expansion.text);
+        assert_eq!(out, Some("Deploy api to prod".to_string()));
+
+        let compiled = CompiledPrompt::new(prompts[0].clone());
+        let submitted =
+            expand_if_numeric_with_positional_args(&compiled, "/prompts:deploy api", &[])
+                .map(|expansion| expansion.text);
+        assert_eq!(submitted, Some("Deploy api to prod".to_string()));
+
+        let required = CompiledPrompt::new(CustomPrompt {
+            content: "Deploy $1 to $ENV".to_string(),
+            ..prompts[0].clone()
+        });
+        assert_eq!(
+            expand_if_numeric_with_positional_args(&required, "/prompts:deploy api", &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn braced_named_placeholder_default_can_read_env() {
+        let content = "Deploy to ${REGION:-$env.AWS_REGION} as ${ROLE:-$env.UNSET_ROLE}.";
+        let env = |name: &str| (name == "AWS_REGION").then(|| "us-west-2".to_string());
+
+        let placeholders = named_placeholders(content);
+        let (text, _) = expand_placeholders_with_elements(
+            content,
+            &placeholders,
+            &HashMap::<String, String>::new(),
+            None,
+            &env,
+        );
+        assert_eq!(text, "Deploy to us-west-2 as .");
+
+        let inputs = HashMap::from([(
+            "REGION".to_string(),
+            PromptArg {
+                text: "eu-central-1".to_string(),
+                text_elements: Vec::new(),
+            },
+        )]);
+        let (text, _) =
+            expand_placeholders_with_elements(content, &placeholders, &inputs, None, &env);
+        assert_eq!(text, "Deploy to eu-central-1 as .");
+    }
+
+    #[test]
+    fn shell_style_aliases_expand_like_arguments() {
+        for content in ["Got: $@", "Got: $*"] {
+            let prompts = vec![CustomPrompt {
+                name: "say".to_string(),
+                path: "/tmp/say.md".to_string().into(),
+                content: content.to_string(),
+                description: None,
+                argument_hint: None,
+                default_args: None,
+            }];
+            assert!(prompt_has_numeric_placeholders(content));
+
+            let out = expand_custom_prompt("/prompts:say a b", &[], &compile(&prompts)).unwrap();
+            assert_eq!(
+                out.map(|expansion| expansion.text),
+                Some("Got: a b".to_string())
+            );
+        }
+
+        let escaped = expand_numeric_placeholders("Literal $$@ and $$*", &[]);
+        assert_eq!(escaped.text, "Literal $$@ and $$*");
+    }
+
+    #[test]
+    fn argument_hint_description_keeps_spaces_and_strips_quotes() {
+        let hint = r#"[USER=<the reviewer>] [PR_TITLE="<title>"] [BRANCH]"#;
+        assert_eq!(
+            argument_hint_description(hint, "USER"),
+            Some("<the reviewer>".to_string())
+        );
+        assert_eq!(
+            argument_hint_description(hint, "PR_TITLE"),
+            Some("<title>".to_string())
+        );
+        assert_eq!(argument_hint_description(hint, "BRANCH"), None);
+        assert_eq!(
+            argument_hint_description(r#"USER="<the reviewer>" TOPIC=<topic>"#, "USER"),
+            Some("<the reviewer>".to_string())
+        );
+    }
+
+    #[test]
+    fn prompt_arg_manifest_combines_required_defaults_and_hints() {
+        let prompt = CustomPrompt {
+            name: "review".to_string(),
+            path: "/tmp/review.md".to_string().into(),
+            content: "Review $USER on ${BRANCH:-main} about $TOPIC".to_string(),
+            description: None,
+            argument_hint: Some("[USER=<reviewer>] [BRANCH] [TOPIC]".to_string()),
+            default_args: None,
+        };
+
+        assert_eq!(
+            prompt_arg_manifest(&prompt),
+            vec![
+                PromptArgInfo {
+                    name: "USER".to_string(),
+                    required: true,
+                    description: Some("<reviewer>".to_string()),
+                    default: None,
+                },
+                PromptArgInfo {
+                    name: "BRANCH".to_string(),
+                    required: false,
+                    description: None,
+                    default: Some("main".to_string()),
+                },
+                PromptArgInfo {
+                    name: "TOPIC".to_string(),
+                    required: true,
+                    description: None,
+                    default: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn default_args_fill_omitted_named_placeholders() {
+        let prompts = vec![CustomPrompt {
+            name: "deploy".to_string(),
+            path: "/tmp/deploy.md".to_string().into(),
+            content: "Deploy $SERVICE from $BRANCH".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: Some(HashMap::from([("BRANCH".to_string(), "main".to_string())])),
+        }];
+
+        assert_eq!(
+            required_prompt_args(&prompts[0]),
+            vec!["SERVICE".to_string()]
+        );
+        let defaulted =
+            expand_custom_prompt("/prompts:deploy SERVICE=api", &[], &compile(&prompts))
+                .unwrap()
+                .map(|expansion| expansion.text);
+        assert_eq!(defaulted, Some("Deploy api from main".to_string()));
+
+        let explicit = expand_custom_prompt(
+            "/prompts:deploy SERVICE=api BRANCH=dev",
+            &[],
+            &compile(&prompts),
+        )
+        .unwrap()
+        .map(|expansion| expansion.text);
+        assert_eq!(explicit, Some("Deploy api from dev".to_string()));
+    }
+
+    #[test]
+    fn braced_positional_placeholders_fall_back_to_defaults() {
+        let content = "Deploy ${1:-api} to ${2:-prod}, literal $${1:-x}";
+        assert!(prompt_has_numeric_placeholders("Deploy ${1:-api}"));
+
+        let provided = expand_numeric_placeholders(
+            content,
+            &[
+                PromptArg {
+                    text: "web".to_string(),
+                    text_elements: Vec::new(),
+                },
+                PromptArg {
+                    text: "staging".to_string(),
+                    text_elements: Vec::new(),
+                },
+            ],
+        );
+        assert_eq!(provided.text, "Deploy web to staging, literal $${1:-x}");
+
+        let defaulted = expand_numeric_placeholders(content, &[]);
+        assert_eq!(defaulted.text, "Deploy api to prod, literal $${1:-x}");
+
+        let bare = expand_numeric_placeholders("Deploy [$1]", &[]);
+        assert_eq!(bare.text, "Deploy []");
+    }
+
+    #[test]
+    fn compiled_prompt_expands_repeatedly() {
+        let prompts = vec![CustomPrompt {
+            name: "review".to_string(),
+            path: "/tmp/review.md".to_string().into(),
+            content: "Review $USER on ${BRANCH:-main}, cc $$OWNER and $USER again".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+        let inputs = HashMap::from([(
+            "USER".to_string(),
+            PromptArg {
+                text: "Alice".to_string(),
+                text_elements: Vec::new(),
+            },
+        )]);
+
+        let compiled = CompiledPrompt::new(prompts[0].clone());
+        assert_eq!(compiled.required(), ["USER".to_string()]);
+
+        let mixed = CustomPrompt {
+            content: "${A:-x} $B $$C $A $D".to_string(),
+            default_args: Some(HashMap::from([("D".to_string(), "d".to_string())])),
+            ..prompts[0].clone()
+        };
+        assert_eq!(
+            CompiledPrompt::new(mixed).required(),
+            ["A".to_string(), "B".to_string()]
+        );
+        let expected = PromptExpansion {
+            text: "Review Alice on main, cc $$OWNER and Alice again".to_string(),
+            text_elements: Vec::new(),
+        };
+        assert_eq!(compiled.expand(&inputs), expected);
+        assert_eq!(compiled.expand(&inputs), expected);
+    }
+
+    #[test]
+    fn backslash_escapes_numeric_placeholders() {
+        let args = vec![PromptArg {
+            text: "x".to_string(),
+            text_elements: Vec::new(),
+        }];
+
+        let escaped = expand_numeric_placeholders(r"cost \$1 and $1", &args);
+        assert_eq!(escaped.text, "cost $1 and x");
+
+        let doubled = expand_numeric_placeholders(r"path \\$1", &args);
+        assert_eq!(doubled.text, r"path \x");
+
+        let plain = expand_numeric_placeholders(r"C:\tmp\ and \n $1", &args);
+        assert_eq!(plain.text, r"C:\tmp\ and \n x");
+
+        let untouched = expand_numeric_placeholders(r"a\\$x, \$x, \$$ and \${1} for $1", &args);
+        assert_eq!(untouched.text, r"a\\$x, \$x, \$$ and \${1} for x");
+
+        let braced = expand_numeric_placeholders(r"\${1:-y} \\${1:-y}", &[]);
+        assert_eq!(braced.text, r"${1:-y} \y");
+
+        assert!(!prompt_has_numeric_placeholders(r"\$$1"));
+        assert_eq!(expand_numeric_placeholders(r"\$$1", &args).text, r"\$$1");
+    }
+
+    #[test]
+    fn backslash_escapes_named_placeholders() {
+        let prompts = vec![CustomPrompt {
+            name: "pay".to_string(),
+            path: "/tmp/pay.md".to_string().into(),
+            content: r"Pay \$USER, not $USER; path \\$USER and \${TEAM:-core}".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+
+        assert_eq!(prompt_argument_names(r"Only \$USER"), Vec::<String>::new());
+        assert_eq!(
+            prompt_argument_names(r"Only \\$USER"),
+            vec!["USER".to_string()]
+        );
+        let expanded = expand_custom_prompt("/prompts:pay USER=Alice", &[], &compile(&prompts))
+            .unwrap()
+            .map(|expansion| expansion.text);
+        assert_eq!(
+            expanded,
+            Some(r"Pay $USER, not Alice; path \Alice and ${TEAM:-core}".to_string())
+        );
+
+        assert!(!prompt_has_numeric_placeholders(
+            r"cost \$1 and \$ARGUMENTS"
+        ));
+        assert!(prompt_has_numeric_placeholders(r"path \\$1"));
+    }
+
+    #[test]
+    fn numeric_detection_matches_expansion() {
+        for content in [
+            "${1}",
+            "${1:-x",
+            "${ARGUMENTS|bogus}",
+            "${ARGUMENTS",
+            "$$1 and $$@",
+        ] {
+            assert!(!prompt_has_numeric_placeholders(content), "{content}");
+            assert_eq!(expand_numeric_placeholders(content, &[]).text, content);
+        }
+        for content in ["${1:-x}", "${ARGUMENTS}", "${ARGUMENTS:-all}", "$$$1"] {
+            assert!(prompt_has_numeric_placeholders(content), "{content}");
+        }
+    }
+
+    #[test]
+    fn argument_count_placeholder_expands_to_arg_count() {
+        for (rest, expected) in [("", "0"), ("one", "1"), ("a \"b c\" d", "3")] {
+            let args = parse_positional_args(rest, &[]);
+            assert_eq!(positional_count(rest).to_string(), expected);
+            assert_eq!(
+                expand_numeric_placeholders("Got $# args, literal $$#", &args).text,
+                format!("Got {expected} args, literal $$#")
+            );
+        }
+        assert!(prompt_has_numeric_placeholders("Got $# args"));
+    }
+
+    #[test]
+    fn expand_with_inputs_uses_values_verbatim() {
+        let prompt = CustomPrompt {
+            name: "note".to_string(),
+            path: "/tmp/note.md".to_string().into(),
+            content: "Title: $TITLE\nBody: $BODY".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        };
+        let inputs = HashMap::from([
+            ("TITLE".to_string(), "Fix \"quoted\" a=b".to_string()),
+            (
+                "BODY".to_string(),
+                "it's $1 & more\nsecond line".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            expand_custom_prompt_with_inputs(&CompiledPrompt::new(prompt.clone()), &inputs)
+                .unwrap()
+                .text,
+            "Title: Fix \"quoted\" a=b\nBody: it's $1 & more\nsecond line"
+        );
+
+        let no_inputs = HashMap::<String, String>::new();
+        let err =
+            expand_custom_prompt_with_inputs(&CompiledPrompt::new(prompt), &no_inputs).unwrap_err();
+        let PromptExpansionError::MissingArgs { command, missing } = err else {
+            panic!("expected MissingArgs, got {err:?}");
+        };
+        assert_eq!(command, "/prompts:note");
+        assert_eq!(missing, vec!["TITLE".to_string(), "BODY".to_string()]);
+    }
+
+    #[test]
+    fn prompt_arg_specs_capture_defaults_and_requiredness() {
+        let content = "Ship ${SERVICE:-api} to $ENV on ${BRANCH:-main}, cc $$OWNER and $SERVICE";
+        assert_eq!(
+            prompt_arg_specs(content),
+            vec![
+                PromptArgSpec {
+                    name: "SERVICE".to_string(),
+                    default: Some("api".to_string()),
+                    required: true,
+                },
+                PromptArgSpec {
+                    name: "ENV".to_string(),
+                    default: None,
+                    required: true,
+                },
+                PromptArgSpec {
+                    name: "BRANCH".to_string(),
+                    default: Some("main".to_string()),
+                    required: false,
+                },
+            ]
+        );
+        assert_eq!(
+            prompt_argument_names(content),
+            vec!["SERVICE".to_string(), "ENV".to_string()]
+        );
+    }
+
+    #[test]
+    fn prompt_inputs_accept_gnu_style_flags() {
+        let args = parse_prompt_inputs("--user \"Alice Smith\" --branch=main NOTE=ok", &[])
+            .expect("inputs");
+        let text = |key: &str| args.get(key).map(|arg| arg.text.as_str());
+        assert_eq!(text("USER"), Some("Alice Smith"));
+        assert_eq!(text("BRANCH"), Some("main"));
+        assert_eq!(text("NOTE"), Some("ok"));
+
+        let prompts = vec![CustomPrompt {
+            name: "review".to_string(),
+            path: "/tmp/review.md".to_string().into(),
+            content: "Review $USER on $BRANCH ($DRY_RUN)".to_string(),
+            description: None,
+            argument_hint: None,
+            default_args: None,
+        }];
+        let out = expand_custom_prompt(
+            "/prompts:review --user Alice --branch=main --dry-run yes",
+            &[],
+            &compile(&prompts),
+        )
+        .unwrap();
+        assert_eq!(
+            out.map(|expansion| expansion.text),
+            Some("Review Alice on main (yes)".to_string())
+        );
+    }
+
+    #[test]
+    fn prompt_inputs_reject_malformed_flags() {
+        assert!(matches!(
+            parse_prompt_inputs("--", &[]),
+            Err(PromptArgsError::MissingKey { token }) if token == "--"
+        ));
+        assert!(matches!(
+            parse_prompt_inputs("--user", &[]),
+            Err(PromptArgsError::MissingAssignment { token }) if token == "--user"
+        ));
+        assert!(matches!(
+            parse_prompt_inputs("--user --branch=main", &[]),
+            Err(PromptArgsError::MissingAssignment { token }) if token == "--user"
+        ));
+        assert!(matches!(
+            parse_prompt_inputs("--dry-run USER=Alice", &[]),
+            Err(PromptArgsError::MissingAssignment { token }) if token == "--dry-run"
+        ));
+        let inputs = parse_prompt_inputs("--note a=b USER=Alice", &[]).unwrap();
+        assert_eq!(inputs["NOTE"].text, "a=b");
+        assert_eq!(inputs["USER"].text, "Alice");
+    }
 }